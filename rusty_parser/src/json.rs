@@ -0,0 +1,163 @@
+use crate::parser::{
+    choice, either, literal_match, pair, quoted_string, right, separated_list, surrounded_by,
+    whitespace_wrap, BoxedParser, ParseError, ParseErrorKind, ParseResult, Parser,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+fn json_null<'a>() -> impl Parser<'a, Value> {
+    literal_match("null").mapper(|_| Value::Null)
+}
+
+fn json_bool<'a>() -> impl Parser<'a, Value> {
+    either(
+        literal_match("true").mapper(|_| Value::Bool(true)),
+        literal_match("false").mapper(|_| Value::Bool(false)),
+    )
+}
+
+fn json_number<'a>() -> impl Parser<'a, Value> {
+    move |input: &'a str| {
+        let mut remaining = input;
+        let mut text = String::new();
+
+        if remaining.starts_with('-') {
+            text.push('-');
+            remaining = &remaining[1..];
+        }
+
+        let digits_start_len = remaining.len();
+        while let Some(c) = remaining.chars().next() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            text.push(c);
+            remaining = &remaining[1..];
+        }
+
+        if remaining.len() == digits_start_len {
+            return Err(ParseError::new(input, ParseErrorKind::Unknown));
+        }
+
+        if remaining.starts_with('.') {
+            text.push('.');
+            remaining = &remaining[1..];
+            while let Some(c) = remaining.chars().next() {
+                if !c.is_ascii_digit() {
+                    break;
+                }
+                text.push(c);
+                remaining = &remaining[1..];
+            }
+        }
+
+        if let Some(exponent) = remaining.chars().next().filter(|c| *c == 'e' || *c == 'E') {
+            text.push(exponent);
+            remaining = &remaining[1..];
+
+            if let Some(sign) = remaining.chars().next().filter(|c| *c == '+' || *c == '-') {
+                text.push(sign);
+                remaining = &remaining[1..];
+            }
+
+            while let Some(c) = remaining.chars().next() {
+                if !c.is_ascii_digit() {
+                    break;
+                }
+                text.push(c);
+                remaining = &remaining[1..];
+            }
+        }
+
+        match text.parse::<f64>() {
+            Ok(value) => Ok((remaining, Value::Number(value))),
+            Err(_) => Err(ParseError::new(input, ParseErrorKind::Unknown)),
+        }
+    }
+}
+
+fn json_string<'a>() -> impl Parser<'a, Value> {
+    quoted_string().mapper(Value::String)
+}
+
+fn json_array<'a>() -> impl Parser<'a, Value> {
+    // json_value() recurses back into json_array()/json_object(), so the recursive call
+    // must stay behind this closure rather than being built eagerly in this function's
+    // body, or constructing json_array() alone would recurse forever.
+    (move |input: &'a str| {
+        surrounded_by(
+            separated_list(whitespace_wrap(json_value()), literal_match(",")),
+            "[",
+            "]",
+        )
+        .parse(input)
+    })
+    .mapper(Value::Array)
+}
+
+fn json_member<'a>() -> impl Parser<'a, (String, Value)> {
+    pair(
+        whitespace_wrap(quoted_string()),
+        right(whitespace_wrap(literal_match(":")), json_value()),
+    )
+}
+
+fn json_object<'a>() -> impl Parser<'a, Value> {
+    // Deferred for the same reason as json_array: json_member() calls json_value()
+    // directly, so this body must not call it eagerly.
+    (move |input: &'a str| {
+        surrounded_by(separated_list(json_member(), literal_match(",")), "{", "}").parse(input)
+    })
+    .mapper(Value::Object)
+}
+
+pub fn json_value<'a>() -> impl Parser<'a, Value> {
+    whitespace_wrap(choice(vec![
+        BoxedParser::new(json_null()),
+        BoxedParser::new(json_bool()),
+        BoxedParser::new(json_number()),
+        BoxedParser::new(json_string()),
+        BoxedParser::new(json_array()),
+        BoxedParser::new(json_object()),
+    ]))
+}
+
+pub fn parse_json<'a>(input: &'a str) -> ParseResult<'a, Value> {
+    BoxedParser::new(json_value()).parse_complete(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_scalars() {
+        assert_eq!(parse_json("1").unwrap().1, Value::Number(1.0));
+        assert_eq!(parse_json("null").unwrap().1, Value::Null);
+        assert_eq!(parse_json("\"hi\"").unwrap().1, Value::String("hi".to_string()));
+    }
+
+    #[test]
+    fn parses_nested_arrays_and_objects() {
+        let (remaining, value) = parse_json("[1, {\"a\": [true, null]}]").unwrap();
+        assert!(remaining.is_empty());
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::Number(1.0),
+                Value::Object(vec![(
+                    "a".to_string(),
+                    Value::Array(vec![Value::Bool(true), Value::Null])
+                )]),
+            ])
+        );
+    }
+}
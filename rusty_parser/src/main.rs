@@ -1,198 +1,21 @@
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct Element {
-    name: String,
-    attr: Vec<String>,
-    children: Vec<Element>,
-}
-
-type ParseResult<'a, Output> = Result<(&'a str, Output), &'a str>;
-
-trait Parser<'a, Output> {
-    fn parse(&self, input: &'a str) -> ParseResult<'a, Output>;
-}
-
-impl<'a, F, Output> Parser<'a, Output> for F
-where
-    F: Fn(&'a str) -> ParseResult<'a, Output>,
-{
-    fn parse(&self, input: &'a str) -> ParseResult<'a, Output> {
-        self(input)
-    }
-}
-
-fn literal_match<'a>(expected: &'static str) -> impl Parser<'a, ()> {
-    move |input: &'a str| match input.get(0..expected.len()) {
-        Some(next) if next == expected => Ok((&input[expected.len()..], ())),
-        _ => Err(input),
-    }
-}
-
-fn non_literal(input: &str) -> ParseResult<String> {
-    let mut matched = String::new();
-    let mut chars = input.chars().peekable();
-
-    while let Some(&c) = chars.peek() {
-        if c.is_alphanumeric() || c == '-' || c == '&' {
-            matched.push(c);
-            chars.next();
-        } else {
-            break;
-        }
-    }
-
-    if matched.is_empty() {
-        return Err(input);
-    }
-
-    let next_index = matched.len();
-    Ok((&input[next_index..], matched))
-}
-
-fn pair<'a, P1, P2, R1, R2>(
-    parser1: P1,
-    parser2: P2,
-) -> impl Parser<'a, (R1, R2)>
-where
-    P1: Parser<'a, R1>,
-    P2: Parser<'a, R2>,
-{
-    move |input| match parser1.parse(input) {
-        Ok((next_input, result1)) => match parser2.parse(next_input) {
-            Ok((final_input, result2)) => Ok((final_input, (result1, result2))),
-            Err(err) => Err(err),
-        },
-        Err(err) => Err(err),
-    }
-}
+mod command;
+mod json;
+mod parser;
 
-fn map<'a, A, B, P, F>(parser: P, f: F) -> impl Parser<'a, B>
-where
-    P: Parser<'a, A>,
-    F: Fn(A) -> B + 'a,
-{
-    move |input| parser.parse(input).map(|(next_input, result)| (next_input, f(result)))
-}
-
-fn left<'a, P1, P2, R1, R2>(parser1: P1, parser2: P2) -> impl Parser<'a, R1>
-where
-    P1: Parser<'a, R1>,
-    P2: Parser<'a, R2>,
-{
-    map(pair(parser1, parser2), |(left, _right)| left)
-}
-
-fn right<'a, P1, P2, R1, R2>(parser1: P1, parser2: P2) -> impl Parser<'a, R2>
-where
-    P1: Parser<'a, R1>,
-    P2: Parser<'a, R2>,
-{
-    map(pair(parser1, parser2), |(_left, right)| right)
-}
-
-fn one_or_more<'a, P, A>(parser: P) -> impl Parser<'a, Vec<A>>
-where
-    P: Parser<'a, A>,
-{
-    move |mut input| {
-        let mut result = Vec::new();
-
-        let (next_input, first_item) = parser.parse(input)?;
-        result.push(first_item);
-        input = next_input;
-
-        while let Ok((next_input, next_item)) = parser.parse(input) {
-            input = next_input;
-            result.push(next_item);
-        }
-
-        Ok((input, result))
-    }
-}
-
-fn zero_or_more<'a, P, A>(parser: P) -> impl Parser<'a, Vec<A>>
-where
-    P: Parser<'a, A>,
-{
-    move |mut input| {
-        let mut result = Vec::new();
-
-        while let Ok((next_input, next_item)) = parser.parse(input) {
-            input = next_input;
-            result.push(next_item);
-        }
-
-        Ok((input, result))
-    }
-}
-
-fn any_char(input: &str) -> ParseResult<char> {
-    match input.chars().next() {
-        Some(next) => Ok((&input[next.len_utf8()..], next)),
-        _ => Err(input),
-    }
-}
-
-fn pred<'a, P, A, F>(parser: P, predicate: F) -> impl Parser<'a, A>
-where
-    P: Parser<'a, A>,
-    F: Fn(&A) -> bool + 'a,
-{
-    move |input| {
-        if let Ok((next_input, value)) = parser.parse(input) {
-            if predicate(&value) {
-                return Ok((next_input, value));
-            }
-        }
-        Err(input)
-    }
-}
-
-fn whitespace_char<'a>() -> impl Parser<'a, char> {
-    pred(any_char, |c| c.is_whitespace())
-}
-
-fn space1<'a>() -> impl Parser<'a, Vec<char>> {
-    one_or_more(whitespace_char())
-}
-
-fn space0<'a>() -> impl Parser<'a, Vec<char>> {
-    zero_or_more(whitespace_char())
-}
-
-fn quoted_string<'a>() -> impl Parser<'a, String> {
-    map(
-        right(
-            literal_match("\""),
-            left(
-                zero_or_more(pred(any_char, |c| *c != '"')),
-                literal_match("\""),
-            ),
-        ),
-        |chars| chars.into_iter().collect(),
-    )
-}
-
-fn attribute_pair<'a>() -> impl Parser<'a, (String, String)> {
-    pair(non_literal, right(literal_match("="), quoted_string()))
-}
-
-fn attributes<'a>() -> impl Parser<'a, Vec<(String, String)>> {
-    zero_or_more(right(space1(), attribute_pair()))
-}
+use std::env;
+use std::process;
 
 fn main() {
-    // Simple test: match "hello"
-    let parser = literal_match("hello");
-    let result = parser.parse("hello world");
-    println!("Parse result: {:?}", result);
+    let input = env::args().skip(1).collect::<Vec<_>>().join(" ");
 
-    // Match an identifier (non-literal)
-    let result = non_literal("my-tag&extra ");
-    println!("Non-literal result: {:?}", result);
+    let elements = match parser::parse_all(&input) {
+        Ok((_, elements)) => elements,
+        Err(err) => {
+            eprintln!("Failed to parse command: {:?}", err);
+            process::exit(1);
+        }
+    };
 
-    // Pair example: match "<" + identifier
-    let tag_parser = pair(literal_match("<"), non_literal);
-    let result = tag_parser.parse("<tag123>");
-    println!("Tag parse result: {:?}", result);
+    let args = command::elements_to_args(&elements);
+    command::run_cli_command(args);
 }
-